@@ -0,0 +1,81 @@
+use serde::Deserialize;
+
+/// One entry from a Chromium DevTools `/json` target list.
+#[derive(Debug, Deserialize)]
+pub struct CdpTarget {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub target_type: String,
+    pub title: String,
+    #[serde(rename = "webSocketDebuggerUrl")]
+    pub ws_url: Option<String>,
+}
+
+/// Pulls `--remote-debugging-port=<N>` out of a process command line — the
+/// same flag `headless_chrome`'s process module looks for when attaching to
+/// an already-running browser instead of launching its own.
+pub fn debug_port_from_cmd(cmd: &str) -> Option<u16> {
+    cmd.split_whitespace()
+        .find_map(|arg| arg.strip_prefix("--remote-debugging-port="))
+        .and_then(|v| v.parse().ok())
+}
+
+/// Fetches the open target list from a browser's DevTools HTTP endpoint.
+pub fn list_targets(port: u16) -> Option<Vec<CdpTarget>> {
+    let url = format!("http://127.0.0.1:{}/json", port);
+    ureq::get(&url).call().ok()?.into_json().ok()
+}
+
+/// Sends `Target.closeTarget` over a target's WebSocket debugger URL,
+/// returning whether Chrome reported success.
+pub fn close_target(ws_url: &str, target_id: &str) -> bool {
+    let Ok((mut socket, _)) = tungstenite::connect(ws_url) else {
+        return false;
+    };
+
+    let request = serde_json::json!({
+        "id": 1,
+        "method": "Target.closeTarget",
+        "params": { "targetId": target_id },
+    });
+
+    if socket
+        .send(tungstenite::Message::Text(request.to_string()))
+        .is_err()
+    {
+        return false;
+    }
+
+    matches!(
+        socket.read(),
+        Ok(tungstenite::Message::Text(resp)) if resp.contains("\"success\":true")
+    )
+}
+
+/// Checks whether a target is backgrounded via the Page Visibility API
+/// (`document.visibilityState !== 'visible'`) — the same signal the page
+/// itself would see, and a real per-tab idle indicator rather than a guess
+/// based on list position.
+pub fn is_hidden(ws_url: &str) -> Option<bool> {
+    let (mut socket, _) = tungstenite::connect(ws_url).ok()?;
+
+    let request = serde_json::json!({
+        "id": 1,
+        "method": "Runtime.evaluate",
+        "params": {
+            "expression": "document.visibilityState !== 'visible'",
+            "returnByValue": true,
+        },
+    });
+
+    socket
+        .send(tungstenite::Message::Text(request.to_string()))
+        .ok()?;
+
+    let tungstenite::Message::Text(resp) = socket.read().ok()? else {
+        return None;
+    };
+
+    let parsed: serde_json::Value = serde_json::from_str(&resp).ok()?;
+    parsed["result"]["result"]["value"].as_bool()
+}
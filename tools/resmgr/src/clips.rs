@@ -0,0 +1,241 @@
+use crate::config::Clips;
+use crate::monitor::{PressureLevel, SystemSnapshot};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// A lightweight, owned copy of the fields of a `SystemSnapshot` worth keeping
+/// around in the ring buffer and writing out to a clip file. We don't hold on
+/// to the `SystemSnapshot` itself because it owns a `Vec<ProcessInfo>` per
+/// sample and 150 of those is wasteful to retain.
+#[derive(Debug, Clone)]
+pub struct ClipSample {
+    pub timestamp: String,
+    pub pressure: PressureLevel,
+    pub used_memory_gb: f64,
+    pub free_memory_gb: f64,
+    pub used_swap_gb: f64,
+    pub node_total_mb: u64,
+    pub browser_total_mb: u64,
+    pub dev_server_total_mb: u64,
+}
+
+impl ClipSample {
+    fn from_snapshot(timestamp: String, snapshot: &SystemSnapshot) -> Self {
+        Self {
+            timestamp,
+            pressure: snapshot.pressure,
+            used_memory_gb: snapshot.used_memory_gb,
+            free_memory_gb: snapshot.free_memory_gb,
+            used_swap_gb: snapshot.used_swap_gb,
+            node_total_mb: snapshot.node_total_mb,
+            browser_total_mb: snapshot.browser_total_mb,
+            dev_server_total_mb: snapshot.dev_server_total_mb,
+        }
+    }
+
+    fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{:.2},{:.2},{:.2},{},{},{}",
+            self.timestamp,
+            self.pressure,
+            self.used_memory_gb,
+            self.free_memory_gb,
+            self.used_swap_gb,
+            self.node_total_mb,
+            self.browser_total_mb,
+            self.dev_server_total_mb,
+        )
+    }
+
+    fn to_json_object(&self) -> String {
+        format!(
+            "{{\"timestamp\":\"{}\",\"pressure\":\"{}\",\"used_memory_gb\":{:.2},\"free_memory_gb\":{:.2},\"used_swap_gb\":{:.2},\"node_total_mb\":{},\"browser_total_mb\":{},\"dev_server_total_mb\":{}}}",
+            self.timestamp,
+            self.pressure,
+            self.used_memory_gb,
+            self.free_memory_gb,
+            self.used_swap_gb,
+            self.node_total_mb,
+            self.browser_total_mb,
+            self.dev_server_total_mb,
+        )
+    }
+}
+
+const CSV_HEADER: &str =
+    "timestamp,pressure,used_memory_gb,free_memory_gb,used_swap_gb,node_total_mb,browser_total_mb,dev_server_total_mb";
+
+/// Two-speed sampler state: a fixed-size ring buffer of recent samples plus
+/// the bookkeeping needed to flip the caller's poll interval into "fast" mode
+/// for a bounded window around an interesting event, and dump the buffer to
+/// a clip file when an event fires.
+pub struct ClipRecorder {
+    config: Clips,
+    buffer: VecDeque<ClipSample>,
+    fast_until: Option<Instant>,
+}
+
+impl ClipRecorder {
+    pub fn new(config: Clips) -> Self {
+        Self {
+            config,
+            buffer: VecDeque::new(),
+            fast_until: None,
+        }
+    }
+
+    /// Record a sample into the ring buffer, evicting the oldest entry once
+    /// `buffer_len` is reached.
+    pub fn record(&mut self, timestamp: String, snapshot: &SystemSnapshot) {
+        if !self.config.enabled {
+            return;
+        }
+        if self.buffer.len() >= self.config.buffer_len {
+            self.buffer.pop_front();
+        }
+        self.buffer
+            .push_back(ClipSample::from_snapshot(timestamp, snapshot));
+    }
+
+    /// Whether the current snapshot is interesting enough to justify
+    /// switching into fast-poll mode: pressure above Elevated, or free
+    /// memory within 10% of the elevated threshold.
+    pub fn is_interesting(&self, snapshot: &SystemSnapshot, elevated_free_gb: f64) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        let margin = elevated_free_gb * 0.1;
+        snapshot.pressure > PressureLevel::Elevated
+            || snapshot.free_memory_gb < elevated_free_gb + margin
+    }
+
+    /// Extend the fast-poll window from now.
+    pub fn enter_fast_window(&mut self) {
+        self.fast_until =
+            Some(Instant::now() + Duration::from_secs(self.config.fast_window_secs));
+    }
+
+    /// Whether we're currently inside a fast-poll window.
+    pub fn is_fast(&self) -> bool {
+        self.fast_until
+            .map(|until| Instant::now() < until)
+            .unwrap_or(false)
+    }
+
+    /// The interval the caller's loop should sleep for on this tick.
+    pub fn poll_interval(&self, normal: Duration) -> Duration {
+        if self.is_fast() {
+            Duration::from_millis(self.config.fast_poll_ms)
+        } else {
+            normal
+        }
+    }
+
+    fn clips_dir(&self) -> PathBuf {
+        PathBuf::from(&self.config.dir)
+    }
+
+    /// Dump the current ring buffer to a timestamped clip file, then rotate
+    /// old clips beyond `max_clips`. The clip holds the samples recorded up
+    /// to and including the triggering event; samples taken after it are not
+    /// included.
+    pub fn dump_clip(&self, reason: &str) -> Option<PathBuf> {
+        if !self.config.enabled || self.buffer.is_empty() {
+            return None;
+        }
+
+        let dir = self.clips_dir();
+        if let Err(e) = fs::create_dir_all(&dir) {
+            log::warn!("Failed to create clips dir {}: {}", dir.display(), e);
+            return None;
+        }
+
+        let slug = slugify(reason);
+        let stamp = self
+            .buffer
+            .back()
+            .map(|s| s.timestamp.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        let base = dir.join(format!("clip-{}-{}", sanitize_for_path(&stamp), slug));
+
+        let csv_path = base.with_extension("csv");
+        let mut csv_body = String::from(CSV_HEADER);
+        csv_body.push('\n');
+        for sample in &self.buffer {
+            csv_body.push_str(&sample.to_csv_row());
+            csv_body.push('\n');
+        }
+        if let Err(e) = fs::write(&csv_path, csv_body) {
+            log::warn!("Failed to write clip {}: {}", csv_path.display(), e);
+        }
+
+        let json_path = base.with_extension("json");
+        let rows: Vec<String> = self.buffer.iter().map(|s| s.to_json_object()).collect();
+        let json_body = format!(
+            "{{\"reason\":\"{}\",\"samples\":[{}]}}",
+            reason.replace('"', "'"),
+            rows.join(",")
+        );
+        if let Err(e) = fs::write(&json_path, json_body) {
+            log::warn!("Failed to write clip {}: {}", json_path.display(), e);
+        }
+
+        self.rotate(&dir);
+
+        log::info!("Wrote clip for '{}': {}", reason, csv_path.display());
+        Some(csv_path)
+    }
+
+    /// Keep only the newest `max_clips` clip files (by pairing .csv/.json
+    /// base names), deleting older ones.
+    fn rotate(&self, dir: &Path) {
+        let mut bases: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().map(|ext| ext == "csv").unwrap_or(false))
+                .collect(),
+            Err(_) => return,
+        };
+
+        bases.sort();
+        let max = self.config.max_clips;
+        if bases.len() <= max {
+            return;
+        }
+
+        for old in &bases[..bases.len() - max] {
+            let _ = fs::remove_file(old);
+            let _ = fs::remove_file(old.with_extension("json"));
+        }
+    }
+
+    /// List clip CSV files, newest last.
+    pub fn list_clips(&self) -> Vec<PathBuf> {
+        let dir = self.clips_dir();
+        let mut bases: Vec<PathBuf> = match fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().map(|ext| ext == "csv").unwrap_or(false))
+                .collect(),
+            Err(_) => return Vec::new(),
+        };
+        bases.sort();
+        bases
+    }
+}
+
+fn slugify(reason: &str) -> String {
+    reason
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn sanitize_for_path(timestamp: &str) -> String {
+    timestamp.replace([':', ' '], "-")
+}
@@ -2,7 +2,7 @@ use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct Config {
     #[serde(default = "default_general")]
     pub general: General,
@@ -16,13 +16,21 @@ pub struct Config {
     pub safe_kill_patterns: SafeKillPatterns,
     #[serde(default)]
     pub trending: Trending,
+    #[serde(default)]
+    pub clips: Clips,
+    #[serde(default)]
+    #[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+    pub scripting: Scripting,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct General {
     #[serde(default = "default_poll_interval")]
     pub poll_interval_seconds: u64,
+    /// Reserved for a future `RUST_LOG`-equivalent filter; logging verbosity
+    /// is currently controlled via the `RUST_LOG` env var only.
     #[serde(default = "default_log_level")]
+    #[allow(dead_code)]
     pub log_level: String,
 }
 
@@ -42,14 +50,52 @@ pub struct Thresholds {
     pub critical_swap_gb: f64,
 }
 
+/// A single rung of a reclamation ladder, from gentlest to most destructive.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReclaimAction {
+    /// Graceful SIGTERM; the process gets `grace_period_secs` to exit on
+    /// its own before the ladder escalates.
+    Sigterm,
+    /// SIGSTOP the process, freezing its working set without losing it.
+    Suspend,
+    /// Log a balloon-style reclaim request for a detected VM guest. No
+    /// hypervisor call is made and the rung never counts as having taken
+    /// effect, so the ladder moves on once the grace period lapses.
+    Balloon,
+    /// SIGKILL — the last rung, when softer steps didn't recover memory.
+    Kill,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct AutoKill {
     #[serde(default = "default_idle_minutes")]
     pub idle_dev_server_minutes: u64,
+    /// Reserved toggle for unconditionally reclaiming orphaned `node`
+    /// processes (see `safe_kill_patterns.orphans`); no reclaim path reads
+    /// it yet.
     #[serde(default = "default_true")]
+    #[allow(dead_code)]
     pub orphan_node_always: bool,
     #[serde(default = "default_true")]
     pub zombie_vms: bool,
+    /// Reclamation ladder applied to idle dev servers at Elevated pressure.
+    #[serde(default = "default_elevated_ladder")]
+    pub elevated_ladder: Vec<ReclaimAction>,
+    /// Reclamation ladder applied to idle dev servers at High pressure.
+    #[serde(default = "default_high_ladder")]
+    pub high_ladder: Vec<ReclaimAction>,
+    /// Reclamation ladder applied to zombie VMs before a hard kill.
+    #[serde(default = "default_zombie_vm_ladder")]
+    pub zombie_vm_ladder: Vec<ReclaimAction>,
+    /// Grace period between escalating from one rung to the next.
+    #[serde(default = "default_sigterm_grace_secs")]
+    pub grace_period_secs: u64,
+    /// Close idle background tabs over the DevTools Protocol instead of
+    /// merely recommending it. Off by default since it requires the browser
+    /// to have been launched with `--remote-debugging-port`.
+    #[serde(default)]
+    pub idle_browser_tabs: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -62,7 +108,10 @@ pub struct Protected {
 pub struct SafeKillPatterns {
     #[serde(default = "default_dev_servers")]
     pub dev_servers: Vec<String>,
+    /// Name patterns for the not-yet-implemented orphan-node reclaim path
+    /// gated by `auto_kill.orphan_node_always`.
     #[serde(default = "default_orphans")]
+    #[allow(dead_code)]
     pub orphans: Vec<String>,
     #[serde(default = "default_zombie_vm_patterns")]
     pub zombie_vms: Vec<String>,
@@ -76,6 +125,44 @@ pub struct Trending {
     pub csv_path: String,
     #[serde(default = "default_trending_interval")]
     pub write_every_n_polls: u64,
+    /// "csv" appends to `csv_path` as before; "sqlite" stores samples and
+    /// kill events in an embedded, queryable history database instead.
+    #[serde(default = "default_trending_backend")]
+    pub backend: String,
+    #[serde(default = "default_trending_db_path")]
+    pub db_path: String,
+}
+
+/// Event-triggered "clip" recorder: a slow poll most of the time, with a
+/// bounded burst of fast polling around interesting pressure events so we
+/// keep high-resolution forensic context without recording everything.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Clips {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_clips_dir")]
+    pub dir: String,
+    #[serde(default = "default_clips_buffer_len")]
+    pub buffer_len: usize,
+    #[serde(default = "default_clips_fast_poll_ms")]
+    pub fast_poll_ms: u64,
+    #[serde(default = "default_clips_fast_window_secs")]
+    pub fast_window_secs: u64,
+    #[serde(default = "default_clips_max_clips")]
+    pub max_clips: usize,
+}
+
+/// Lua scripting hooks (requires the `scripting` feature). Lets site-specific
+/// kill policy — e.g. never kill a build running right now — be expressed
+/// without recompiling resmgr.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Scripting {
+    #[serde(default)]
+    #[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+    pub enabled: bool,
+    #[serde(default)]
+    #[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+    pub script: Option<String>,
 }
 
 // Defaults
@@ -113,6 +200,18 @@ fn default_critical_swap() -> f64 {
 fn default_idle_minutes() -> u64 {
     10
 }
+fn default_elevated_ladder() -> Vec<ReclaimAction> {
+    vec![ReclaimAction::Suspend]
+}
+fn default_high_ladder() -> Vec<ReclaimAction> {
+    vec![ReclaimAction::Sigterm, ReclaimAction::Kill]
+}
+fn default_zombie_vm_ladder() -> Vec<ReclaimAction> {
+    vec![ReclaimAction::Balloon, ReclaimAction::Kill]
+}
+fn default_sigterm_grace_secs() -> u64 {
+    15
+}
 fn default_true() -> bool {
     true
 }
@@ -122,6 +221,27 @@ fn default_trending_path() -> String {
 fn default_trending_interval() -> u64 {
     2 // Write every 2 polls = every 60s at default 30s interval
 }
+fn default_trending_backend() -> String {
+    "csv".to_string()
+}
+fn default_trending_db_path() -> String {
+    "/tmp/resmgr_history.db".to_string()
+}
+fn default_clips_dir() -> String {
+    "/tmp/resmgr_clips".to_string()
+}
+fn default_clips_buffer_len() -> usize {
+    150
+}
+fn default_clips_fast_poll_ms() -> u64 {
+    200
+}
+fn default_clips_fast_window_secs() -> u64 {
+    30
+}
+fn default_clips_max_clips() -> usize {
+    20
+}
 
 fn default_protected_processes() -> Vec<String> {
     vec![
@@ -180,6 +300,11 @@ impl Default for AutoKill {
             idle_dev_server_minutes: default_idle_minutes(),
             orphan_node_always: true,
             zombie_vms: true,
+            elevated_ladder: default_elevated_ladder(),
+            high_ladder: default_high_ladder(),
+            zombie_vm_ladder: default_zombie_vm_ladder(),
+            grace_period_secs: default_sigterm_grace_secs(),
+            idle_browser_tabs: false,
         }
     }
 }
@@ -208,19 +333,21 @@ impl Default for Trending {
             enabled: true,
             csv_path: default_trending_path(),
             write_every_n_polls: default_trending_interval(),
+            backend: default_trending_backend(),
+            db_path: default_trending_db_path(),
         }
     }
 }
 
-impl Default for Config {
+impl Default for Clips {
     fn default() -> Self {
         Self {
-            general: General::default(),
-            thresholds: Thresholds::default(),
-            auto_kill: AutoKill::default(),
-            protected: Protected::default(),
-            safe_kill_patterns: SafeKillPatterns::default(),
-            trending: Trending::default(),
+            enabled: true,
+            dir: default_clips_dir(),
+            buffer_len: default_clips_buffer_len(),
+            fast_poll_ms: default_clips_fast_poll_ms(),
+            fast_window_secs: default_clips_fast_window_secs(),
+            max_clips: default_clips_max_clips(),
         }
     }
 }
@@ -0,0 +1,58 @@
+/// Desktop notification backend. `Monitor`'s memory/process sampling already
+/// runs on `sysinfo`, which is portable, so the last macOS-only piece of the
+/// daemon was how it surfaces alerts to the user. This trait abstracts that
+/// over a macOS backend (`mac_notification_sys`) and a Linux backend
+/// (desktop-environment notifications via `notify-send`).
+pub trait Notifier {
+    fn notify(&self, title: &str, body: &str);
+}
+
+#[cfg(target_os = "macos")]
+pub struct MacNotifier;
+
+#[cfg(target_os = "macos")]
+impl Notifier for MacNotifier {
+    fn notify(&self, title: &str, body: &str) {
+        if let Err(e) = mac_notification_sys::send_notification(title, None, body, None) {
+            log::warn!("Failed to send notification: {}", e);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub struct LinuxNotifier;
+
+#[cfg(target_os = "linux")]
+impl Notifier for LinuxNotifier {
+    fn notify(&self, title: &str, body: &str) {
+        use std::process::Command;
+        if let Err(e) = Command::new("notify-send").arg(title).arg(body).status() {
+            log::warn!("Failed to send desktop notification via notify-send: {}", e);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub struct NullNotifier;
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+impl Notifier for NullNotifier {
+    fn notify(&self, _title: &str, _body: &str) {
+        log::debug!("Desktop notifications are not supported on this platform");
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn platform_notifier() -> impl Notifier {
+    MacNotifier
+}
+
+#[cfg(target_os = "linux")]
+pub fn platform_notifier() -> impl Notifier {
+    LinuxNotifier
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn platform_notifier() -> impl Notifier {
+    NullNotifier
+}
@@ -0,0 +1,159 @@
+use crate::monitor::SystemSnapshot;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+/// Embedded, queryable history store behind `[trending] backend = "sqlite"`.
+/// Supplements (or replaces) the append-only trending CSV with a backend
+/// `resmgr report` can actually roll up — peak/percentile memory, time spent
+/// at each pressure level, and which process families got auto-killed.
+pub struct HistoryStore {
+    conn: Connection,
+}
+
+impl HistoryStore {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS samples (
+                timestamp TEXT NOT NULL,
+                pressure TEXT NOT NULL,
+                used_memory_gb REAL NOT NULL,
+                free_memory_gb REAL NOT NULL,
+                used_swap_gb REAL NOT NULL,
+                node_total_mb INTEGER NOT NULL,
+                browser_total_mb INTEGER NOT NULL,
+                dev_server_total_mb INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS kill_events (
+                timestamp TEXT NOT NULL,
+                family TEXT NOT NULL,
+                name TEXT NOT NULL,
+                pid INTEGER NOT NULL,
+                memory_mb INTEGER NOT NULL
+            );",
+        )?;
+        Ok(Self { conn })
+    }
+
+    pub fn record_sample(&self, timestamp: &str, snapshot: &SystemSnapshot) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO samples
+                (timestamp, pressure, used_memory_gb, free_memory_gb, used_swap_gb,
+                 node_total_mb, browser_total_mb, dev_server_total_mb)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                timestamp,
+                snapshot.pressure.to_string(),
+                snapshot.used_memory_gb,
+                snapshot.free_memory_gb,
+                snapshot.used_swap_gb,
+                snapshot.node_total_mb,
+                snapshot.browser_total_mb,
+                snapshot.dev_server_total_mb,
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn record_kill(
+        &self,
+        timestamp: &str,
+        family: &str,
+        name: &str,
+        pid: u32,
+        memory_mb: u64,
+    ) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO kill_events (timestamp, family, name, pid, memory_mb) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![timestamp, family, name, pid, memory_mb],
+        )?;
+        Ok(())
+    }
+
+    /// Rollups since `cutoff` (an ISO timestamp — they sort lexically, same
+    /// as the ones `time_source::now_iso` produces).
+    pub fn report(&self, cutoff: &str) -> rusqlite::Result<Report> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT pressure, used_memory_gb FROM samples WHERE timestamp >= ?1 ORDER BY used_memory_gb")?;
+        let rows = stmt.query_map(params![cutoff], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })?;
+
+        let mut used_memory_gb: Vec<f64> = Vec::new();
+        let mut time_at_level: HashMap<String, usize> = HashMap::new();
+        for row in rows {
+            let (pressure, used) = row?;
+            used_memory_gb.push(used);
+            *time_at_level.entry(pressure).or_insert(0) += 1;
+        }
+
+        let peak_used_memory_gb = used_memory_gb.last().copied().unwrap_or(0.0);
+        let p50_used_memory_gb = percentile(&used_memory_gb, 0.50);
+        let p95_used_memory_gb = percentile(&used_memory_gb, 0.95);
+
+        let mut kill_stmt = self.conn.prepare(
+            "SELECT family, COUNT(*), SUM(memory_mb) FROM kill_events WHERE timestamp >= ?1 GROUP BY family",
+        )?;
+        let kill_rows = kill_stmt.query_map(params![cutoff], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, usize>(1)?,
+                row.get::<_, i64>(2)? as u64,
+            ))
+        })?;
+
+        let mut kills_by_family: HashMap<String, (usize, u64)> = HashMap::new();
+        let mut total_reclaimed_mb: u64 = 0;
+        for row in kill_rows {
+            let (family, count, total_mb) = row?;
+            total_reclaimed_mb += total_mb;
+            kills_by_family.insert(family, (count, total_mb));
+        }
+
+        Ok(Report {
+            sample_count: used_memory_gb.len(),
+            peak_used_memory_gb,
+            p50_used_memory_gb,
+            p95_used_memory_gb,
+            time_at_level,
+            kills_by_family,
+            total_reclaimed_mb,
+        })
+    }
+}
+
+/// `sorted` must already be sorted ascending.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let idx = ((sorted.len() - 1) as f64 * pct).round() as usize;
+    sorted[idx]
+}
+
+pub struct Report {
+    pub sample_count: usize,
+    pub peak_used_memory_gb: f64,
+    pub p50_used_memory_gb: f64,
+    pub p95_used_memory_gb: f64,
+    pub time_at_level: HashMap<String, usize>,
+    pub kills_by_family: HashMap<String, (usize, u64)>,
+    pub total_reclaimed_mb: u64,
+}
+
+/// Parse a simple "--since" duration like "30m", "24h", "7d" into a cutoff
+/// ISO timestamp. Hand-rolled, matching the rest of the crate's preference
+/// for not pulling in a parsing crate for something this small.
+pub fn since_to_cutoff(since: &str) -> Option<String> {
+    let since = since.trim();
+    let (num, unit) = since.split_at(since.len().saturating_sub(1));
+    let value: u64 = num.parse().ok()?;
+    let secs = match unit {
+        "m" => value * 60,
+        "h" => value * 3600,
+        "d" => value * 86_400,
+        _ => return None,
+    };
+    Some(crate::time_source::iso_before_now(secs))
+}
@@ -1,7 +1,17 @@
+mod cdp;
+mod clips;
 mod config;
+mod desktop_notify;
+mod history;
+mod mem_accounting;
 mod monitor;
+#[cfg(feature = "scripting")]
+mod scripting;
+mod time_source;
+mod tui;
 
-use config::Config;
+use config::{Config, ReclaimAction};
+use desktop_notify::Notifier;
 use monitor::{Monitor, PressureLevel};
 use std::env;
 use std::fs::OpenOptions;
@@ -9,13 +19,6 @@ use std::io::Write;
 use std::time::Duration;
 use tokio::time;
 
-fn notify(title: &str, body: &str) {
-    match mac_notification_sys::send_notification(title, None, body, None) {
-        Ok(_) => {}
-        Err(e) => log::warn!("Failed to send notification: {}", e),
-    }
-}
-
 fn format_snapshot(snapshot: &monitor::SystemSnapshot) -> String {
     let mut lines = Vec::new();
     lines.push(format!(
@@ -37,12 +40,26 @@ fn format_snapshot(snapshot: &monitor::SystemSnapshot) -> String {
     lines.join("\n")
 }
 
-/// Write a trending data point to CSV
-fn write_trending(config: &config::Trending, snapshot: &monitor::SystemSnapshot) {
+/// Write a trending data point — to the history store when
+/// `[trending] backend = "sqlite"`, otherwise to the append-only CSV.
+fn write_trending(
+    config: &config::Trending,
+    snapshot: &monitor::SystemSnapshot,
+    history: Option<&history::HistoryStore>,
+) {
     if !config.enabled {
         return;
     }
 
+    let now = chrono_lite_timestamp();
+
+    if let Some(store) = history {
+        if let Err(e) = store.record_sample(&now, snapshot) {
+            log::warn!("Failed to record sample to history store: {}", e);
+        }
+        return;
+    }
+
     let path = &config.csv_path;
     let needs_header = !std::path::Path::new(path).exists();
 
@@ -66,7 +83,6 @@ fn write_trending(config: &config::Trending, snapshot: &monitor::SystemSnapshot)
         );
     }
 
-    let now = chrono_lite_timestamp();
     let _ = writeln!(
         file,
         "{},{},{:.2},{:.2},{:.2},{},{},{},{},{},{}",
@@ -84,16 +100,66 @@ fn write_trending(config: &config::Trending, snapshot: &monitor::SystemSnapshot)
     );
 }
 
-/// Simple timestamp without pulling in chrono crate
+/// Summarize a set of reclaim actions as "<action> <name> [<pid>] (<mb>MB)".
+fn summarize_reclaim(reclaimed: &[(String, sysinfo::Pid, u64, ReclaimAction)]) -> String {
+    reclaimed
+        .iter()
+        .map(|(name, pid, mb, action)| format!("{:?} {} [{}] ({}MB)", action, name, pid, mb))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Log a round of reclaim-ladder results for a process family, recording
+/// only the rungs that actually killed something into the history store
+/// (softer rungs don't free memory, so they'd distort the reclaimed total).
+/// Returns the total MB freed by any hard kills in this round.
+fn log_reclaim_actions(
+    history: &Option<history::HistoryStore>,
+    vm_reclaimed: &[(String, sysinfo::Pid, u64, ReclaimAction)],
+    dev_reclaimed: &[(String, sysinfo::Pid, u64, ReclaimAction)],
+    swap_reclaimed: &[(String, sysinfo::Pid, u64, ReclaimAction)],
+) -> u64 {
+    let mut total_freed = 0u64;
+
+    for (family, reclaimed) in [
+        ("zombie_vm", vm_reclaimed),
+        ("dev_server", dev_reclaimed),
+        ("swap_offender", swap_reclaimed),
+    ] {
+        if reclaimed.is_empty() {
+            continue;
+        }
+        log::info!("Reclaim actions ({}): {}", family, summarize_reclaim(reclaimed));
+
+        let killed: Vec<(String, sysinfo::Pid, u64)> = reclaimed
+            .iter()
+            .filter(|(_, _, _, action)| *action == ReclaimAction::Kill)
+            .map(|(name, pid, mb, _)| (name.clone(), *pid, *mb))
+            .collect();
+        total_freed += killed.iter().map(|(_, _, mb)| mb).sum::<u64>();
+        record_kills(history.as_ref(), family, &killed);
+    }
+
+    total_freed
+}
+
+/// Record kill events (zombie VMs, idle dev servers or swap offenders) into the history
+/// store, if one is configured.
+fn record_kills(history: Option<&history::HistoryStore>, family: &str, killed: &[(String, sysinfo::Pid, u64)]) {
+    let Some(store) = history else {
+        return;
+    };
+    let now = chrono_lite_timestamp();
+    for (name, pid, mb) in killed {
+        if let Err(e) = store.record_kill(&now, family, name, pid.as_u32(), *mb) {
+            log::warn!("Failed to record kill event to history store: {}", e);
+        }
+    }
+}
+
+/// Simple timestamp without pulling in the chrono crate
 fn chrono_lite_timestamp() -> String {
-    use std::process::Command;
-    Command::new("date")
-        .arg("+%Y-%m-%dT%H:%M:%S")
-        .output()
-        .ok()
-        .and_then(|o| String::from_utf8(o.stdout).ok())
-        .map(|s| s.trim().to_string())
-        .unwrap_or_else(|| "unknown".to_string())
+    time_source::now_iso()
 }
 
 /// One-shot status report — print and exit
@@ -126,28 +192,49 @@ fn run_status(config: &Config) {
 
     println!("--- Aggregates ---");
     println!(
-        "Node:      {} processes, {}MB",
-        snapshot.node_count, snapshot.node_total_mb
+        "Node:      {} processes, {}MB RSS ({}MB unique)",
+        snapshot.node_count, snapshot.node_total_mb, snapshot.node_total_uss_mb
     );
     println!(
-        "Browser:   {} processes, {}MB",
-        snapshot.browser_count, snapshot.browser_total_mb
+        "Browser:   {} processes, {}MB RSS ({}MB unique)",
+        snapshot.browser_count, snapshot.browser_total_mb, snapshot.browser_total_uss_mb
     );
     println!(
-        "DevServer: {} processes, {}MB",
-        snapshot.dev_server_count, snapshot.dev_server_total_mb
+        "DevServer: {} processes, {}MB RSS ({}MB unique)",
+        snapshot.dev_server_count, snapshot.dev_server_total_mb, snapshot.dev_server_total_uss_mb
     );
     println!();
 
     println!("--- Top 10 by Memory ---");
     for (i, p) in snapshot.top_processes.iter().take(10).enumerate() {
+        let subtree = if p.child_pids.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "  [subtree {}MB across {} child(ren)]",
+                p.subtree_memory_mb,
+                p.child_pids.len()
+            )
+        };
+        let swap = if p.swap_mb > 0 {
+            format!("  swap {}MB", p.swap_mb)
+        } else {
+            String::new()
+        };
         println!(
-            "  {:2}. {:>6}MB  {} (PID {})",
+            "  {:2}. {:>6}MB  (pss {}MB, uss {}MB){}  {} (PID {}){}",
             i + 1,
             p.memory_mb,
+            p.pss_mb,
+            p.uss_mb,
+            swap,
             p.name,
-            p.pid
+            p.pid,
+            subtree
         );
+        if !p.cmd.is_empty() && p.cmd != p.name {
+            println!("       {}", p.cmd);
+        }
     }
 
     let recs = monitor.recommendations(&snapshot);
@@ -160,6 +247,102 @@ fn run_status(config: &Config) {
     }
 }
 
+/// List recent clips, or print one if a name/path is given as the next arg.
+fn run_clips(config: &Config, target: Option<&str>) {
+    let recorder = clips::ClipRecorder::new(config.clips.clone());
+
+    if let Some(target) = target {
+        let path = std::path::Path::new(target);
+        let path = if path.is_absolute() || path.exists() {
+            path.to_path_buf()
+        } else {
+            std::path::Path::new(&config.clips.dir).join(target)
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => print!("{}", contents),
+            Err(e) => eprintln!("Failed to read clip {}: {}", path.display(), e),
+        }
+        return;
+    }
+
+    let files = recorder.list_clips();
+    if files.is_empty() {
+        println!("No clips recorded in {}", config.clips.dir);
+        return;
+    }
+
+    println!("=== resmgr clips ({}) ===", config.clips.dir);
+    for path in files {
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        println!("  {} ({} bytes)", path.display(), size);
+    }
+}
+
+/// Roll up the sqlite history store: peak/percentile memory, time spent at
+/// each pressure level, and which process families triggered the most
+/// auto-kills.
+fn run_report(config: &Config, since: &str) {
+    if config.trending.backend != "sqlite" {
+        eprintln!(
+            "resmgr report requires [trending] backend = \"sqlite\" (currently \"{}\")",
+            config.trending.backend
+        );
+        return;
+    }
+
+    let cutoff = match history::since_to_cutoff(since) {
+        Some(c) => c,
+        None => {
+            eprintln!("Invalid --since value '{}', expected e.g. 30m, 24h, 7d", since);
+            return;
+        }
+    };
+
+    let store = match history::HistoryStore::open(&config.trending.db_path) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Failed to open history store {}: {}", config.trending.db_path, e);
+            return;
+        }
+    };
+
+    let report = match store.report(&cutoff) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Failed to query history store: {}", e);
+            return;
+        }
+    };
+
+    println!(
+        "=== resmgr report (since {}, {} samples) ===",
+        since, report.sample_count
+    );
+    println!();
+    println!("Peak used memory: {:.1} GB", report.peak_used_memory_gb);
+    println!("p50 used memory:  {:.1} GB", report.p50_used_memory_gb);
+    println!("p95 used memory:  {:.1} GB", report.p95_used_memory_gb);
+    println!();
+
+    println!("--- Time spent at each pressure level (sample counts) ---");
+    for level in ["Normal", "Elevated", "High", "Critical"] {
+        let count = report.time_at_level.get(level).copied().unwrap_or(0);
+        println!("  {:<9} {}", level, count);
+    }
+    println!();
+
+    println!("--- Auto-kills by family ---");
+    if report.kills_by_family.is_empty() {
+        println!("  (none)");
+    } else {
+        for (family, (count, mb)) in &report.kills_by_family {
+            println!("  {:<12} {} kill(s), ~{}MB reclaimed", family, count, mb);
+        }
+    }
+    println!();
+    println!("Total reclaimed: ~{}MB", report.total_reclaimed_mb);
+}
+
 #[tokio::main]
 async fn main() {
     env_logger::init();
@@ -177,6 +360,45 @@ async fn main() {
         return;
     }
 
+    if args.iter().any(|a| a == "top" || a == "--tui") {
+        let config_path = args
+            .windows(2)
+            .find(|w| w[0] == "--config")
+            .map(|w| w[1].as_str());
+        let config = Config::load(config_path);
+        let basic_mode = args.iter().any(|a| a == "--basic");
+        if let Err(e) = tui::run(config, basic_mode) {
+            eprintln!("resmgr top failed: {}", e);
+        }
+        return;
+    }
+
+    if let Some(pos) = args.iter().position(|a| a == "clips") {
+        let config_path = args
+            .windows(2)
+            .find(|w| w[0] == "--config")
+            .map(|w| w[1].as_str());
+        let config = Config::load(config_path);
+        let target = args.get(pos + 1).map(|s| s.as_str());
+        run_clips(&config, target);
+        return;
+    }
+
+    if args.iter().any(|a| a == "report") {
+        let config_path = args
+            .windows(2)
+            .find(|w| w[0] == "--config")
+            .map(|w| w[1].as_str());
+        let config = Config::load(config_path);
+        let since = args
+            .windows(2)
+            .find(|w| w[0] == "--since")
+            .map(|w| w[1].as_str())
+            .unwrap_or("24h");
+        run_report(&config, since);
+        return;
+    }
+
     // Parse --config flag
     let config_path = args
         .windows(2)
@@ -198,26 +420,58 @@ async fn main() {
         config.thresholds.critical_free_gb,
     );
     if config.trending.enabled {
-        log::info!("Trending CSV: {}", config.trending.csv_path);
+        if config.trending.backend == "sqlite" {
+            log::info!("Trending history store: {}", config.trending.db_path);
+        } else {
+            log::info!("Trending CSV: {}", config.trending.csv_path);
+        }
     }
     if config.auto_kill.zombie_vms {
         log::info!("Zombie VM detection: enabled");
     }
+    if config.clips.enabled {
+        log::info!(
+            "Clip recorder: enabled (dir={}, buffer={}, fast_poll={}ms)",
+            config.clips.dir,
+            config.clips.buffer_len,
+            config.clips.fast_poll_ms
+        );
+    }
 
     let mut monitor = Monitor::new(config.clone());
+    let mut clip_recorder = clips::ClipRecorder::new(config.clips.clone());
+    let notifier = desktop_notify::platform_notifier();
+    let history_store = if config.trending.backend == "sqlite" {
+        match history::HistoryStore::open(&config.trending.db_path) {
+            Ok(store) => Some(store),
+            Err(e) => {
+                log::warn!("Failed to open history store {}: {}", config.trending.db_path, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
     let mut prev_level = PressureLevel::Normal;
     let mut last_notify = std::time::Instant::now() - Duration::from_secs(300);
-    let mut poll_count: u64 = 0;
-
-    let mut interval = time::interval(poll_interval);
+    let trending_interval = poll_interval * config.trending.write_every_n_polls as u32;
+    let mut last_trending_write = std::time::Instant::now() - trending_interval;
 
     loop {
-        interval.tick().await;
-        poll_count += 1;
+        time::sleep(clip_recorder.poll_interval(poll_interval)).await;
 
         let snapshot = monitor.sample();
         let level = snapshot.pressure;
 
+        let clip_timestamp = chrono_lite_timestamp();
+        clip_recorder.record(clip_timestamp, &snapshot);
+        if clip_recorder.is_interesting(&snapshot, config.thresholds.elevated_free_gb) {
+            clip_recorder.enter_fast_window();
+        }
+        if level != prev_level {
+            clip_recorder.dump_clip(&format!("pressure {} -> {}", prev_level, level));
+        }
+
         log::debug!(
             "Memory: {:.1}/{:.1} GB used | Free: {:.1} GB | Swap: {:.1}/{:.1} GB | Pressure: {} | Node: {} ({} MB) | DevServers: {}",
             snapshot.used_memory_gb,
@@ -231,9 +485,14 @@ async fn main() {
             snapshot.dev_server_count,
         );
 
-        // Write trending data
-        if poll_count % config.trending.write_every_n_polls == 0 {
-            write_trending(&config.trending, &snapshot);
+        // Write trending data on a wall-clock cadence, not a raw poll count —
+        // during a fast-poll clip window `poll_interval` shrinks to
+        // `fast_poll_ms`, and gating on iteration count alone would turn
+        // "every ~60s" into "every ~400ms" for the duration of the window.
+        let trending_now = std::time::Instant::now();
+        if trending_now.duration_since(last_trending_write) >= trending_interval {
+            write_trending(&config.trending, &snapshot, history_store.as_ref());
+            last_trending_write = trending_now;
         }
 
         let now = std::time::Instant::now();
@@ -247,11 +506,21 @@ async fn main() {
             }
 
             PressureLevel::Elevated => {
-                // Kill zombie VMs at elevated+ pressure
-                let vm_killed = monitor.kill_zombie_vms();
-                if !vm_killed.is_empty() {
-                    let freed: u64 = vm_killed.iter().map(|(_, _, mb)| mb).sum();
-                    log::info!("Killed zombie VM(s), freed ~{}MB", freed);
+                // Soft reclamation first: suspend idle dev servers and try a
+                // balloon-style VM reclaim. A zombie VM still present after
+                // the grace period moves to the next rung of its ladder,
+                // which is a kill by default.
+                let vm_reclaimed =
+                    monitor.reclaim_zombie_vms(level, &config.auto_kill.zombie_vm_ladder);
+                let dev_reclaimed =
+                    monitor.reclaim_idle_dev_servers(level, &config.auto_kill.elevated_ladder);
+                let swap_reclaimed =
+                    monitor.reclaim_swap_offenders(&snapshot, level, &config.auto_kill.elevated_ladder);
+                log_reclaim_actions(&history_store, &vm_reclaimed, &dev_reclaimed, &swap_reclaimed);
+
+                let closed_tabs = monitor.close_idle_tabs();
+                if !closed_tabs.is_empty() {
+                    log::info!("Closed {} idle browser tab(s)", closed_tabs.len());
                 }
 
                 if (level != prev_level
@@ -259,64 +528,72 @@ async fn main() {
                     && now.duration_since(last_notify) > notify_cooldown
                 {
                     let body = format_snapshot(&snapshot);
-                    notify("Memory Pressure: Elevated", &body);
+                    notifier.notify("Memory Pressure: Elevated", &body);
                     last_notify = now;
                     log::info!("Elevated pressure — {}", body.replace('\n', " | "));
                 }
             }
 
             PressureLevel::High => {
-                // Kill zombie VMs
-                let vm_killed = monitor.kill_zombie_vms();
+                // Escalate the reclamation ladder: SIGTERM idle dev servers
+                // (falling through to SIGKILL once the grace period lapses)
+                // and keep trying to balloon zombie VMs before killing them.
+                let vm_reclaimed = monitor.reclaim_zombie_vms(level, &config.auto_kill.zombie_vm_ladder);
+                let dev_reclaimed =
+                    monitor.reclaim_idle_dev_servers(level, &config.auto_kill.high_ladder);
+                let swap_reclaimed =
+                    monitor.reclaim_swap_offenders(&snapshot, level, &config.auto_kill.high_ladder);
+                let total_freed =
+                    log_reclaim_actions(&history_store, &vm_reclaimed, &dev_reclaimed, &swap_reclaimed);
 
-                // Auto-kill idle dev servers
-                let killed = monitor.kill_idle_dev_servers();
                 let mut body = format_snapshot(&snapshot);
-
-                let mut total_freed: u64 = 0;
-                if !vm_killed.is_empty() {
-                    let freed: u64 = vm_killed.iter().map(|(_, _, mb)| mb).sum();
-                    total_freed += freed;
-                    body.push_str(&format!("\nKilled zombie VM(s), freed ~{}MB", freed));
+                if !vm_reclaimed.is_empty() {
+                    body.push_str(&format!(
+                        "\nVM reclaim actions: {}",
+                        summarize_reclaim(&vm_reclaimed)
+                    ));
                 }
-
-                if !killed.is_empty() {
-                    let freed: u64 = killed.iter().map(|(_, _, mb)| mb).sum();
-                    total_freed += freed;
-                    let names: Vec<String> = killed
-                        .iter()
-                        .map(|(name, pid, mb)| format!("{} [{}] ({}MB)", name, pid, mb))
-                        .collect();
+                if !dev_reclaimed.is_empty() {
                     body.push_str(&format!(
-                        "\nKilled {} idle dev server(s), freed ~{}MB:\n{}",
-                        killed.len(),
-                        freed,
-                        names.join(", ")
+                        "\nDev server reclaim actions: {}",
+                        summarize_reclaim(&dev_reclaimed)
+                    ));
+                }
+                if !swap_reclaimed.is_empty() {
+                    body.push_str(&format!(
+                        "\nSwap offender reclaim actions: {}",
+                        summarize_reclaim(&swap_reclaimed)
                     ));
-                    log::info!("Killed idle dev servers: {:?}", names);
                 }
 
                 if total_freed > 0 {
                     log::info!("Total freed: ~{}MB", total_freed);
+                    clip_recorder.dump_clip("auto-kill at High pressure");
                 }
 
                 if now.duration_since(last_notify) > notify_cooldown {
-                    notify("Memory Pressure: High", &body);
+                    notifier.notify("Memory Pressure: High", &body);
                     last_notify = now;
                 }
             }
 
             PressureLevel::Critical => {
                 // Kill zombie VMs
-                let vm_killed = monitor.kill_zombie_vms();
+                let vm_killed = monitor.kill_zombie_vms(level);
 
                 // Kill ALL dev servers
-                let killed = monitor.kill_idle_dev_servers();
+                let killed = monitor.kill_idle_dev_servers(level);
+
+                // Kill whatever's actually driving the swap thrashing, not
+                // just whatever matches the dev-server/VM pattern lists.
+                let swap_killed = monitor.kill_swap_offenders(&snapshot);
+
                 let mut body = format_snapshot(&snapshot);
 
                 if !vm_killed.is_empty() {
                     let freed: u64 = vm_killed.iter().map(|(_, _, mb)| mb).sum();
                     body.push_str(&format!("\nKilled zombie VM(s), freed ~{}MB", freed));
+                    record_kills(history_store.as_ref(), "zombie_vm", &vm_killed);
                 }
 
                 if !killed.is_empty() {
@@ -326,12 +603,23 @@ async fn main() {
                         killed.len(),
                         freed
                     ));
+                    record_kills(history_store.as_ref(), "dev_server", &killed);
+                }
+
+                if !swap_killed.is_empty() {
+                    let freed: u64 = swap_killed.iter().map(|(_, _, mb)| mb).sum();
+                    body.push_str(&format!(
+                        "\nKilled top swap offender(s), freed ~{}MB swap",
+                        freed
+                    ));
+                    record_kills(history_store.as_ref(), "swap_offender", &swap_killed);
                 }
 
+                clip_recorder.dump_clip("Critical pressure");
                 body.push_str("\nConsider: close browser tabs, quit Docker");
 
                 if now.duration_since(last_notify) > Duration::from_secs(60) {
-                    notify("CRITICAL: Memory Pressure", &body);
+                    notifier.notify("CRITICAL: Memory Pressure", &body);
                     last_notify = now;
                 }
 
@@ -1,9 +1,13 @@
-use crate::config::Config;
+use crate::cdp;
+use crate::config::{Config, ReclaimAction};
+use crate::mem_accounting;
 use std::collections::HashMap;
-use std::time::Instant;
-use sysinfo::{MemoryRefreshKind, Pid, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System};
+use std::time::{Duration, Instant};
+use sysinfo::{
+    MemoryRefreshKind, Pid, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, Signal, System,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PressureLevel {
     Normal,
     Elevated,
@@ -11,6 +15,60 @@ pub enum PressureLevel {
     Critical,
 }
 
+/// First-class process grouping, modeled on Chromium OS's `process_meter`:
+/// buckets processes the same way `about:memory` does. It sits alongside the
+/// separate `node_*`/`browser_*`/`dev_server_*` counters, which
+/// `SystemSnapshot` still carries and `recommendations` still reads; it does
+/// not replace them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessGroup {
+    Browser,
+    Gpu,
+    Renderer,
+    ExtensionUtility,
+    DevServer,
+    Node,
+    Vm,
+}
+
+impl std::fmt::Display for ProcessGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessGroup::Browser => write!(f, "Browser"),
+            ProcessGroup::Gpu => write!(f, "GPU"),
+            ProcessGroup::Renderer => write!(f, "Renderer"),
+            ProcessGroup::ExtensionUtility => write!(f, "Extension/Utility"),
+            ProcessGroup::DevServer => write!(f, "DevServer"),
+            ProcessGroup::Node => write!(f, "Node"),
+            ProcessGroup::Vm => write!(f, "VM"),
+        }
+    }
+}
+
+/// Per-group memory totals broken down by kind, so recommendations can
+/// tell e.g. a browser holding 4GB of reclaimable file cache from one
+/// holding 4GB of anonymous heap that only a kill can reclaim.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GroupStats {
+    pub total_mb: u64,
+    pub anon_mb: u64,
+    pub file_mb: u64,
+    pub shmem_mb: u64,
+    pub swap_mb: u64,
+    pub count: usize,
+}
+
+impl GroupStats {
+    fn add(&mut self, accounting: &mem_accounting::MemoryAccounting, mem_mb: u64) {
+        self.total_mb += mem_mb;
+        self.anon_mb += accounting.anon_mb;
+        self.file_mb += accounting.file_mb;
+        self.shmem_mb += accounting.shmem_mb;
+        self.swap_mb += accounting.swap_mb;
+        self.count += 1;
+    }
+}
+
 impl std::fmt::Display for PressureLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -26,8 +84,22 @@ pub struct ProcessInfo {
     pub pid: Pid,
     pub name: String,
     pub memory_mb: u64,
+    /// Proportional share of resident pages, shared ones divided among their
+    /// sharers — summing this across a process family gives its true
+    /// physical footprint without double-counting, unlike summing RSS.
+    pub pss_mb: u64,
+    /// Private (unshared) resident pages — what killing this process would
+    /// actually return to the system.
+    pub uss_mb: u64,
+    pub swap_mb: u64,
     pub cpu_percent: f32,
     pub cmd: String,
+    /// This process's own memory plus every descendant's, rolled up to
+    /// attribute Chromium renderer/GPU helpers and dev-server workers to
+    /// their launcher rather than leaving them double-counted or orphaned.
+    pub subtree_memory_mb: u64,
+    /// Direct children, per `sysinfo`'s `proc.parent()`.
+    pub child_pids: Vec<Pid>,
 }
 
 pub struct SystemSnapshot {
@@ -40,17 +112,78 @@ pub struct SystemSnapshot {
     pub pressure: PressureLevel,
     pub node_count: usize,
     pub node_total_mb: u64,
+    /// USS total across Node processes — RSS double-counts pages shared
+    /// between sibling `node` workers, as opposed to `node_total_mb`'s raw
+    /// RSS sum.
+    pub node_total_uss_mb: u64,
     pub browser_count: usize,
     pub browser_total_mb: u64,
+    /// USS total across browser processes — RSS double-counts framework/GPU
+    /// pages shared across a browser's helper processes, as opposed to
+    /// `browser_total_mb`'s raw RSS sum.
+    pub browser_total_uss_mb: u64,
     pub dev_server_count: usize,
     pub dev_server_total_mb: u64,
+    /// USS total across dev servers — what killing them would actually
+    /// free, as opposed to `dev_server_total_mb`'s raw RSS sum.
+    pub dev_server_total_uss_mb: u64,
+    /// Dev server launchers' RSS rolled up through their process subtree
+    /// (e.g. a `npm run dev` shell's `node` build workers), root launchers
+    /// only so a restarted dev server's matched child isn't double-counted
+    /// under both itself and its parent.
+    pub dev_server_total_subtree_mb: u64,
+    /// Richer grouping with a per-kind memory breakdown; see `ProcessGroup`.
+    pub group_stats: HashMap<ProcessGroup, GroupStats>,
+    /// Processes ranked by swap footprint (name, pid, swap_mb), highest
+    /// first. A large resident-but-not-swapped process contributes nothing
+    /// to thrashing while a heavily-swapped idle one does, so this ranks
+    /// differently than `top_processes`' RSS sort.
+    pub top_swap_offenders: Vec<(String, Pid, u64)>,
 }
 
+/// Samples memory/swap and per-process stats via `sysinfo`, which reads
+/// `/proc` on Linux and the Mach/libproc APIs on macOS — this part of the
+/// daemon has always been cross-platform. `safe_kill_patterns`/`protected`
+/// matching operates on process name/cmdline strings, so it carries over to
+/// Linux process names unchanged.
 pub struct Monitor {
     system: System,
     config: Config,
     /// Tracks how long each process has been at ~0% CPU
     idle_tracker: HashMap<Pid, Instant>,
+    /// Tracks how far each process has escalated up its reclamation ladder,
+    /// and when it last moved a rung, so we only escalate after a grace
+    /// period instead of jumping straight to a hard kill.
+    reclaim_attempts: HashMap<Pid, (usize, Instant)>,
+    /// How long each open CDP tab target has continuously reported
+    /// `document.visibilityState !== 'visible'`, so `close_idle_tabs` can
+    /// require a real backgrounded duration rather than guessing from list
+    /// order.
+    tab_hidden_since: HashMap<String, Instant>,
+    #[cfg(feature = "scripting")]
+    script_engine: Option<crate::scripting::ScriptEngine>,
+}
+
+/// Memoized post-order rollup of `pid`'s own memory plus every descendant's,
+/// mirroring how Chromium's `memory_details` attributes renderer/GPU
+/// children back to the browser instance that spawned them.
+fn compute_subtree_mb(
+    pid: Pid,
+    children: &HashMap<Pid, Vec<Pid>>,
+    own_mb: &HashMap<Pid, u64>,
+    cache: &mut HashMap<Pid, u64>,
+) -> u64 {
+    if let Some(&cached) = cache.get(&pid) {
+        return cached;
+    }
+    let mut total = *own_mb.get(&pid).unwrap_or(&0);
+    if let Some(kids) = children.get(&pid) {
+        for &child in kids {
+            total += compute_subtree_mb(child, children, own_mb, cache);
+        }
+    }
+    cache.insert(pid, total);
+    total
 }
 
 impl Monitor {
@@ -61,13 +194,46 @@ impl Monitor {
                 .with_processes(ProcessRefreshKind::everything()),
         );
 
+        #[cfg(feature = "scripting")]
+        let script_engine = if config.scripting.enabled {
+            config
+                .scripting
+                .script
+                .as_deref()
+                .and_then(crate::scripting::ScriptEngine::load)
+        } else {
+            None
+        };
+
         Self {
             system,
             config,
             idle_tracker: HashMap::new(),
+            reclaim_attempts: HashMap::new(),
+            tab_hidden_since: HashMap::new(),
+            #[cfg(feature = "scripting")]
+            script_engine,
         }
     }
 
+    /// Consult the scripting hook (if the `scripting` feature is enabled and
+    /// a policy script defines `should_kill`) before terminating a process.
+    /// Defaults to allowing the kill when no script overrides the decision.
+    #[cfg(feature = "scripting")]
+    fn script_should_kill(&self, name: &str, pid: Pid, memory_mb: u64, pressure: PressureLevel) -> bool {
+        match &self.script_engine {
+            Some(engine) => engine
+                .should_kill(name, pid.as_u32(), memory_mb, pressure)
+                .unwrap_or(true),
+            None => true,
+        }
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    fn script_should_kill(&self, _name: &str, _pid: Pid, _memory_mb: u64, _pressure: PressureLevel) -> bool {
+        true
+    }
+
     pub fn sample(&mut self) -> SystemSnapshot {
         self.system.refresh_memory();
         self.system.refresh_processes_specifics(
@@ -84,10 +250,51 @@ impl Monitor {
 
         let mut node_count: usize = 0;
         let mut node_total_mb: u64 = 0;
+        let mut node_total_uss_mb: u64 = 0;
         let mut browser_count: usize = 0;
         let mut browser_total_mb: u64 = 0;
+        let mut browser_total_uss_mb: u64 = 0;
         let mut dev_server_count: usize = 0;
         let mut dev_server_total_mb: u64 = 0;
+        let mut dev_server_total_uss_mb: u64 = 0;
+        let mut group_stats: HashMap<ProcessGroup, GroupStats> = HashMap::new();
+
+        let children_map = self.build_children_map();
+        let own_mem_mb: HashMap<Pid, u64> = self
+            .system
+            .processes()
+            .iter()
+            .map(|(pid, proc)| (*pid, proc.memory() / 1_048_576))
+            .collect();
+        let mut subtree_cache: HashMap<Pid, u64> = HashMap::new();
+        let mut swap_offenders: Vec<(String, Pid, u64)> = Vec::new();
+
+        // Pre-pass so the rollup below can tell a dev-server launcher from
+        // one of its own matched descendants — without it, a dev server
+        // restarted via a matching child process would have its subtree
+        // counted twice (once under itself, once under its parent).
+        let dev_server_pids: std::collections::HashSet<Pid> = self
+            .system
+            .processes()
+            .iter()
+            .filter(|(_, proc)| {
+                let name = proc.name().to_string_lossy().to_string();
+                let cmd = proc
+                    .cmd()
+                    .iter()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                !Self::is_browser_process(&name, &cmd)
+                    && self
+                        .config
+                        .safe_kill_patterns
+                        .dev_servers
+                        .iter()
+                        .any(|p| name.contains(p) || cmd.contains(p))
+            })
+            .map(|(pid, _)| *pid)
+            .collect();
 
         // Collect top processes by memory
         let mut processes: Vec<ProcessInfo> = self
@@ -97,6 +304,14 @@ impl Monitor {
             .map(|(pid, proc)| {
                 let name = proc.name().to_string_lossy().to_string();
                 let mem_mb = proc.memory() / 1_048_576;
+                let accounting = mem_accounting::read_process_accounting(pid.as_u32());
+                let (pss_mb, uss_mb, swap_mb) = match accounting {
+                    // Fall back to RSS when per-process accounting isn't
+                    // available (e.g. permission denied, unsupported OS) so
+                    // callers always get a usable figure.
+                    Some(a) => (a.pss_mb, a.uss_mb, a.swap_mb),
+                    None => (mem_mb, mem_mb, 0),
+                };
                 let cmd = proc
                     .cmd()
                     .iter()
@@ -108,10 +323,12 @@ impl Monitor {
                 if name.contains("node") || name == "node" {
                     node_count += 1;
                     node_total_mb += mem_mb;
+                    node_total_uss_mb += uss_mb;
                 }
                 if name.contains("Brave") || name.contains("Chrome") || name.contains("Firefox") {
                     browser_count += 1;
                     browser_total_mb += mem_mb;
+                    browser_total_uss_mb += uss_mb;
                 }
                 if !Self::is_browser_process(&name, &cmd)
                     && self
@@ -123,21 +340,65 @@ impl Monitor {
                 {
                     dev_server_count += 1;
                     dev_server_total_mb += mem_mb;
+                    dev_server_total_uss_mb += uss_mb;
+                }
+
+                if let Some(group) = self.classify_process(&name, &cmd) {
+                    // Treat unknown splits as fully anonymous — the
+                    // conservative (least reclaimable) assumption.
+                    let kind_split = accounting.unwrap_or(mem_accounting::MemoryAccounting {
+                        anon_mb: mem_mb,
+                        ..Default::default()
+                    });
+                    group_stats.entry(group).or_default().add(&kind_split, mem_mb);
+                }
+
+                let subtree_memory_mb =
+                    compute_subtree_mb(*pid, &children_map, &own_mem_mb, &mut subtree_cache);
+                let child_pids = children_map.get(pid).cloned().unwrap_or_default();
+
+                if swap_mb > 0 {
+                    swap_offenders.push((name.clone(), *pid, swap_mb));
                 }
 
                 ProcessInfo {
                     pid: *pid,
                     name,
                     memory_mb: mem_mb,
+                    pss_mb,
+                    uss_mb,
+                    swap_mb,
                     cpu_percent: proc.cpu_usage(),
                     cmd,
+                    subtree_memory_mb,
+                    child_pids,
                 }
             })
             .collect();
 
-        processes.sort_by(|a, b| b.memory_mb.cmp(&a.memory_mb));
+        processes.sort_by_key(|p| std::cmp::Reverse(p.memory_mb));
         processes.truncate(20);
 
+        swap_offenders.sort_by_key(|o| std::cmp::Reverse(o.2));
+        swap_offenders.truncate(10);
+
+        // Roll each dev server's matched children up to their root launcher
+        // instead of counting every matched node in the tree separately —
+        // mirrors the atomic-subtree-kill behavior in
+        // `kill_idle_dev_servers`, and is what "killing this dev server"
+        // would actually free (its `node` build workers included).
+        let dev_server_total_subtree_mb: u64 = dev_server_pids
+            .iter()
+            .filter(|pid| {
+                self.system
+                    .process(**pid)
+                    .and_then(|proc| proc.parent())
+                    .map(|parent| !dev_server_pids.contains(&parent))
+                    .unwrap_or(true)
+            })
+            .map(|pid| subtree_cache.get(pid).copied().unwrap_or(0))
+            .sum();
+
         // Update idle tracker
         let now = Instant::now();
         let current_pids: Vec<Pid> = self.system.processes().keys().copied().collect();
@@ -145,6 +406,8 @@ impl Monitor {
         // Remove dead processes
         self.idle_tracker
             .retain(|pid, _| current_pids.contains(pid));
+        self.reclaim_attempts
+            .retain(|pid, _| current_pids.contains(pid));
 
         // Track idle processes (CPU < 0.5%)
         for (pid, proc) in self.system.processes() {
@@ -167,10 +430,16 @@ impl Monitor {
             pressure,
             node_count,
             node_total_mb,
+            node_total_uss_mb,
             browser_count,
             browser_total_mb,
+            browser_total_uss_mb,
             dev_server_count,
             dev_server_total_mb,
+            dev_server_total_uss_mb,
+            dev_server_total_subtree_mb,
+            group_stats,
+            top_swap_offenders: swap_offenders,
         }
     }
 
@@ -196,6 +465,30 @@ impl Monitor {
             .any(|p| name.contains(p))
     }
 
+    /// Builds a parent PID -> direct children PIDs map from `sysinfo`'s
+    /// `proc.parent()`, used to roll helper-process memory up to its
+    /// launcher and to terminate an idle subtree atomically.
+    fn build_children_map(&self) -> HashMap<Pid, Vec<Pid>> {
+        let mut children_map: HashMap<Pid, Vec<Pid>> = HashMap::new();
+        for (pid, proc) in self.system.processes() {
+            if let Some(parent) = proc.parent() {
+                children_map.entry(parent).or_default().push(*pid);
+            }
+        }
+        children_map
+    }
+
+    /// All PIDs in `pid`'s subtree, `pid` itself included.
+    fn collect_subtree_pids(pid: Pid, children: &HashMap<Pid, Vec<Pid>>) -> Vec<Pid> {
+        let mut result = vec![pid];
+        if let Some(kids) = children.get(&pid) {
+            for &child in kids {
+                result.extend(Self::collect_subtree_pids(child, children));
+            }
+        }
+        result
+    }
+
     /// Check if a process is a browser/Chromium-based process by name or command line.
     /// Chromium spawns many helper subprocesses (renderer, GPU, utility, network service)
     /// whose command lines can contain arbitrary strings — we must never match these
@@ -238,6 +531,51 @@ impl Monitor {
         false
     }
 
+    /// Bucket a process into a `ProcessGroup` for per-group memory-kind
+    /// reporting. Browser helper process types are distinguished by their
+    /// `--type=` Chromium command-line flag; dev-server/VM matching reuses
+    /// the existing name/cmd pattern lists.
+    fn classify_process(&self, name: &str, cmd: &str) -> Option<ProcessGroup> {
+        if Self::is_browser_process(name, cmd) {
+            if cmd.contains("--type=gpu-process") {
+                return Some(ProcessGroup::Gpu);
+            }
+            if cmd.contains("--type=renderer") {
+                return Some(ProcessGroup::Renderer);
+            }
+            if cmd.contains("--type=utility") || cmd.contains("--type=extension") {
+                return Some(ProcessGroup::ExtensionUtility);
+            }
+            return Some(ProcessGroup::Browser);
+        }
+
+        if self
+            .config
+            .safe_kill_patterns
+            .dev_servers
+            .iter()
+            .any(|p| name.contains(p) || cmd.contains(p))
+        {
+            return Some(ProcessGroup::DevServer);
+        }
+
+        if self
+            .config
+            .safe_kill_patterns
+            .zombie_vms
+            .iter()
+            .any(|p| name.contains(p) || cmd.contains(p))
+        {
+            return Some(ProcessGroup::Vm);
+        }
+
+        if name.contains("node") {
+            return Some(ProcessGroup::Node);
+        }
+
+        None
+    }
+
     /// Kill a process by PID, returning true if successful
     pub fn kill_process(&self, pid: Pid) -> bool {
         if let Some(proc) = self.system.process(pid) {
@@ -271,12 +609,192 @@ impl Monitor {
         }
     }
 
+    /// How far up `ladder` a process has escalated, advancing one rung once
+    /// `grace` has elapsed since it last moved. Stays on the last rung once
+    /// reached rather than wrapping or overflowing.
+    fn escalation_stage(&mut self, pid: Pid, ladder_len: usize, grace: Duration) -> usize {
+        let now = Instant::now();
+        let entry = self.reclaim_attempts.entry(pid).or_insert((0, now));
+        if now.duration_since(entry.1) >= grace && entry.0 + 1 < ladder_len {
+            entry.0 += 1;
+            entry.1 = now;
+        }
+        entry.0.min(ladder_len.saturating_sub(1))
+    }
+
+    /// Apply a single reclamation rung to a process, returning whether it
+    /// took effect.
+    fn apply_reclaim_action(&self, pid: Pid, name: &str, action: ReclaimAction) -> bool {
+        match action {
+            ReclaimAction::Sigterm => self
+                .system
+                .process(pid)
+                .and_then(|proc| proc.kill_with(Signal::Term))
+                .unwrap_or(false),
+            ReclaimAction::Suspend => self
+                .system
+                .process(pid)
+                .and_then(|proc| proc.kill_with(Signal::Stop))
+                .unwrap_or(false),
+            ReclaimAction::Balloon => self.request_vm_balloon(pid, name),
+            ReclaimAction::Kill => self.kill_process(pid),
+        }
+    }
+
+    /// Placeholder for a hypervisor balloon-driver call: no balloon API is
+    /// wired up yet, so this just logs the intent and lets the ladder
+    /// escalate to a hard kill if pressure persists past the grace period.
+    fn request_vm_balloon(&self, pid: Pid, name: &str) -> bool {
+        log::info!(
+            "Requesting balloon-style memory reclaim from VM guest {} (PID {}) — falling through to kill if pressure persists",
+            name,
+            pid
+        );
+        false
+    }
+
+    /// Apply a graduated reclamation ladder to idle dev servers instead of
+    /// jumping straight to SIGKILL: SIGTERM with a grace period, optionally
+    /// SIGSTOP to freeze the working set, only falling through to a hard
+    /// kill once softer rungs haven't recovered enough memory.
+    pub fn reclaim_idle_dev_servers(
+        &mut self,
+        pressure: PressureLevel,
+        ladder: &[ReclaimAction],
+    ) -> Vec<(String, Pid, u64, ReclaimAction)> {
+        if ladder.is_empty() {
+            return Vec::new();
+        }
+
+        let grace = Duration::from_secs(self.config.auto_kill.grace_period_secs);
+        let idle_threshold =
+            Duration::from_secs(self.config.auto_kill.idle_dev_server_minutes * 60);
+        let now = Instant::now();
+
+        let targets: Vec<(Pid, String, u64)> = self
+            .system
+            .processes()
+            .iter()
+            .filter_map(|(pid, proc)| {
+                let proc_name = proc.name().to_string_lossy().to_string();
+                let cmd = proc
+                    .cmd()
+                    .iter()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                if Self::is_browser_process(&proc_name, &cmd) {
+                    return None;
+                }
+
+                let is_dev_server = self
+                    .config
+                    .safe_kill_patterns
+                    .dev_servers
+                    .iter()
+                    .any(|pattern| proc_name.contains(pattern) || cmd.contains(pattern));
+
+                if !is_dev_server || self.is_protected(&proc_name) {
+                    return None;
+                }
+
+                let idle_since = self.idle_tracker.get(pid)?;
+                if now.duration_since(*idle_since) < idle_threshold {
+                    return None;
+                }
+
+                Some((*pid, proc_name, proc.memory() / 1_048_576))
+            })
+            .collect();
+
+        let mut applied = Vec::new();
+        for (pid, name, mem_mb) in targets {
+            if !self.script_should_kill(&name, pid, mem_mb, pressure) {
+                continue;
+            }
+            let stage = self.escalation_stage(pid, ladder.len(), grace);
+            let action = ladder[stage];
+            if self.apply_reclaim_action(pid, &name, action) {
+                log::info!("Reclaim: applied {:?} to {} (PID {})", action, name, pid);
+                applied.push((name, pid, mem_mb, action));
+            }
+        }
+
+        applied
+    }
+
+    /// Same graduated ladder, applied to zombie VMs: try a balloon-style
+    /// soft reclaim before resorting to a hard kill.
+    pub fn reclaim_zombie_vms(
+        &mut self,
+        pressure: PressureLevel,
+        ladder: &[ReclaimAction],
+    ) -> Vec<(String, Pid, u64, ReclaimAction)> {
+        if ladder.is_empty() || !self.config.auto_kill.zombie_vms {
+            return Vec::new();
+        }
+
+        let docker_active = self.system.processes().values().any(|proc| {
+            let name = proc.name().to_string_lossy().to_string();
+            name.contains("com.docker.backend") || name.contains("Docker Desktop")
+        });
+        if docker_active {
+            return Vec::new();
+        }
+
+        let grace = Duration::from_secs(self.config.auto_kill.grace_period_secs);
+
+        let targets: Vec<(Pid, String, u64)> = self
+            .system
+            .processes()
+            .iter()
+            .filter_map(|(pid, proc)| {
+                let proc_name = proc.name().to_string_lossy().to_string();
+                let cmd = proc
+                    .cmd()
+                    .iter()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                let is_vm = self
+                    .config
+                    .safe_kill_patterns
+                    .zombie_vms
+                    .iter()
+                    .any(|pattern| proc_name.contains(pattern) || cmd.contains(pattern));
+
+                if !is_vm {
+                    return None;
+                }
+                Some((*pid, proc_name, proc.memory() / 1_048_576))
+            })
+            .collect();
+
+        let mut applied = Vec::new();
+        for (pid, name, mem_mb) in targets {
+            if !self.script_should_kill(&name, pid, mem_mb, pressure) {
+                continue;
+            }
+            let stage = self.escalation_stage(pid, ladder.len(), grace);
+            let action = ladder[stage];
+            if self.apply_reclaim_action(pid, &name, action) {
+                log::info!("Reclaim: applied {:?} to zombie VM {} (PID {})", action, name, pid);
+                applied.push((name, pid, mem_mb, action));
+            }
+        }
+
+        applied
+    }
+
     /// Find and kill idle dev servers, returning list of (name, pid, memory_mb) killed
-    pub fn kill_idle_dev_servers(&mut self) -> Vec<(String, Pid, u64)> {
+    pub fn kill_idle_dev_servers(&mut self, pressure: PressureLevel) -> Vec<(String, Pid, u64)> {
         let idle_threshold =
             std::time::Duration::from_secs(self.config.auto_kill.idle_dev_server_minutes * 60);
         let now = Instant::now();
         let mut killed = Vec::new();
+        let children_map = self.build_children_map();
 
         let targets: Vec<(Pid, String, u64)> = self
             .system
@@ -315,7 +833,11 @@ impl Monitor {
                 // Check idle time
                 if let Some(idle_since) = self.idle_tracker.get(pid) {
                     if now.duration_since(*idle_since) >= idle_threshold {
-                        return Some((*pid, proc_name, proc.memory() / 1_048_576));
+                        let mem_mb = proc.memory() / 1_048_576;
+                        if !self.script_should_kill(&proc_name, *pid, mem_mb, pressure) {
+                            return None;
+                        }
+                        return Some((*pid, proc_name, mem_mb));
                     }
                 }
 
@@ -323,9 +845,33 @@ impl Monitor {
             })
             .collect();
 
-        for (pid, name, mem_mb) in targets {
-            if self.kill_process(pid) {
-                killed.push((name, pid, mem_mb));
+        // Kill each idle dev server's entire subtree atomically — a bare
+        // `npm run dev` left behind would otherwise orphan its `node`
+        // build workers — re-checking the browser/protected guards on
+        // every node, since a launcher's children aren't necessarily
+        // covered by the checks already applied to the launcher itself.
+        for (root_pid, _root_name, _root_mem_mb) in targets {
+            for node_pid in Self::collect_subtree_pids(root_pid, &children_map) {
+                let Some(proc) = self.system.process(node_pid) else {
+                    continue;
+                };
+                let node_name = proc.name().to_string_lossy().to_string();
+                let node_cmd = proc
+                    .cmd()
+                    .iter()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                if Self::is_browser_process(&node_name, &node_cmd) || self.is_protected(&node_name)
+                {
+                    continue;
+                }
+
+                let node_mem_mb = proc.memory() / 1_048_576;
+                if self.kill_process(node_pid) {
+                    killed.push((node_name, node_pid, node_mem_mb));
+                }
             }
         }
 
@@ -333,7 +879,7 @@ impl Monitor {
     }
 
     /// Detect and kill zombie Virtualization VMs (Docker quit but VM still running)
-    pub fn kill_zombie_vms(&self) -> Vec<(String, Pid, u64)> {
+    pub fn kill_zombie_vms(&self, pressure: PressureLevel) -> Vec<(String, Pid, u64)> {
         if !self.config.auto_kill.zombie_vms {
             return Vec::new();
         }
@@ -373,7 +919,11 @@ impl Monitor {
                     .any(|pattern| proc_name.contains(pattern) || cmd.contains(pattern));
 
                 if is_vm {
-                    Some((*pid, proc_name, proc.memory() / 1_048_576))
+                    let mem_mb = proc.memory() / 1_048_576;
+                    if !self.script_should_kill(&proc_name, *pid, mem_mb, pressure) {
+                        return None;
+                    }
+                    Some((*pid, proc_name, mem_mb))
                 } else {
                     None
                 }
@@ -390,28 +940,262 @@ impl Monitor {
         killed
     }
 
+    /// Finds a running Chromium-family browser's main process launched with
+    /// `--remote-debugging-port`, returning its name and the port.
+    fn find_browser_debug_port(&self) -> Option<(String, u16)> {
+        self.system.processes().values().find_map(|proc| {
+            let name = proc.name().to_string_lossy().to_string();
+            let cmd = proc
+                .cmd()
+                .iter()
+                .map(|s| s.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !Self::is_browser_process(&name, &cmd) || name.contains("Helper") {
+                return None;
+            }
+            cdp::debug_port_from_cmd(&cmd).map(|port| (name, port))
+        })
+    }
+
+    /// Closes idle background tabs over the DevTools Protocol, gated behind
+    /// `auto_kill.idle_browser_tabs`, so browser memory can be reclaimed
+    /// surgically without killing the whole browser (which `kill_process`
+    /// always refuses to do). A tab only qualifies once it has continuously
+    /// reported itself backgrounded (`document.visibilityState !== 'visible'`
+    /// via the Page Visibility API, queried over its own debugger WebSocket)
+    /// for at least `idle_dev_server_minutes` — the same real per-target
+    /// idle signal `idle_tracker` provides for processes, rather than
+    /// guessing from the `/json` list's order.
+    pub fn close_idle_tabs(&mut self) -> Vec<(String, String)> {
+        if !self.config.auto_kill.idle_browser_tabs {
+            return Vec::new();
+        }
+
+        let Some((browser_name, port)) = self.find_browser_debug_port() else {
+            return Vec::new();
+        };
+
+        let Some(targets) = cdp::list_targets(port) else {
+            return Vec::new();
+        };
+
+        let page_targets: Vec<&cdp::CdpTarget> =
+            targets.iter().filter(|t| t.target_type == "page").collect();
+
+        let live_ids: std::collections::HashSet<&str> =
+            page_targets.iter().map(|t| t.id.as_str()).collect();
+        self.tab_hidden_since
+            .retain(|id, _| live_ids.contains(id.as_str()));
+
+        let idle_threshold =
+            Duration::from_secs(self.config.auto_kill.idle_dev_server_minutes * 60);
+        let now = Instant::now();
+
+        let mut closed = Vec::new();
+        for target in page_targets {
+            let Some(ws_url) = &target.ws_url else {
+                continue;
+            };
+
+            if !cdp::is_hidden(ws_url).unwrap_or(false) {
+                self.tab_hidden_since.remove(&target.id);
+                continue;
+            }
+
+            let hidden_since = *self
+                .tab_hidden_since
+                .entry(target.id.clone())
+                .or_insert(now);
+            if now.duration_since(hidden_since) < idle_threshold {
+                continue;
+            }
+
+            if cdp::close_target(ws_url, &target.id) {
+                log::info!(
+                    "Closed idle background tab '{}' in {} (hidden {:?})",
+                    target.title,
+                    browser_name,
+                    now.duration_since(hidden_since)
+                );
+                closed.push((browser_name.clone(), target.title.clone()));
+                self.tab_hidden_since.remove(&target.id);
+            }
+        }
+
+        closed
+    }
+
+    /// Ranks kill candidates by swap footprint rather than RSS, for use at
+    /// `High`/`Critical` pressure: a large resident-but-not-swapped process
+    /// contributes nothing to thrashing, while a heavily-swapped idle one
+    /// does, so reclaiming it is what actually relieves the thrash.
+    /// `kill_process`'s own protected/browser guards still apply to every
+    /// candidate returned here.
+    pub fn swap_kill_candidates(
+        &self,
+        snapshot: &SystemSnapshot,
+        pressure: PressureLevel,
+    ) -> Vec<(String, Pid, u64)> {
+        if pressure < PressureLevel::High {
+            return Vec::new();
+        }
+
+        snapshot
+            .top_swap_offenders
+            .iter()
+            .filter(|(name, _, _)| !self.is_protected(name))
+            .cloned()
+            .collect()
+    }
+
+    /// Candidates from `swap_kill_candidates` that aren't already handled by
+    /// `reclaim_idle_dev_servers`/`reclaim_zombie_vms` (dev-server and VM
+    /// pattern matches), capped to the top few so a whole top-10 swap list
+    /// doesn't all get acted on in one pass.
+    fn unclaimed_swap_candidates(&self, snapshot: &SystemSnapshot, pressure: PressureLevel) -> Vec<(String, Pid, u64)> {
+        const MAX_TARGETS: usize = 3;
+
+        self.swap_kill_candidates(snapshot, pressure)
+            .into_iter()
+            .filter(|(name, pid, _)| {
+                let cmd = self
+                    .system
+                    .process(*pid)
+                    .map(|proc| {
+                        proc.cmd()
+                            .iter()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .unwrap_or_default();
+
+                if Self::is_browser_process(name, &cmd) {
+                    return false;
+                }
+
+                let already_covered = self
+                    .config
+                    .safe_kill_patterns
+                    .dev_servers
+                    .iter()
+                    .chain(self.config.safe_kill_patterns.zombie_vms.iter())
+                    .any(|pattern| name.contains(pattern) || cmd.contains(pattern));
+
+                !already_covered
+            })
+            .take(MAX_TARGETS)
+            .collect()
+    }
+
+    /// Applies the reclamation ladder to the top swap-footprint offenders —
+    /// the processes whose reclaim actually relieves thrashing, per the
+    /// request, which may belong to neither the dev-server nor VM pattern
+    /// lists that `reclaim_idle_dev_servers`/`reclaim_zombie_vms` cover.
+    pub fn reclaim_swap_offenders(
+        &mut self,
+        snapshot: &SystemSnapshot,
+        pressure: PressureLevel,
+        ladder: &[ReclaimAction],
+    ) -> Vec<(String, Pid, u64, ReclaimAction)> {
+        if ladder.is_empty() {
+            return Vec::new();
+        }
+
+        let grace = Duration::from_secs(self.config.auto_kill.grace_period_secs);
+        let candidates = self.unclaimed_swap_candidates(snapshot, pressure);
+
+        let mut applied = Vec::new();
+        for (name, pid, swap_mb) in candidates {
+            let Some(proc) = self.system.process(pid) else {
+                continue;
+            };
+            let mem_mb = proc.memory() / 1_048_576;
+            if !self.script_should_kill(&name, pid, mem_mb, pressure) {
+                continue;
+            }
+
+            let stage = self.escalation_stage(pid, ladder.len(), grace);
+            let action = ladder[stage];
+            if self.apply_reclaim_action(pid, &name, action) {
+                log::info!(
+                    "Reclaim: applied {:?} to top swap offender {} (PID {}, {}MB swapped)",
+                    action,
+                    name,
+                    pid,
+                    swap_mb
+                );
+                applied.push((name, pid, mem_mb, action));
+            }
+        }
+
+        applied
+    }
+
+    /// Immediately kills the top swap-footprint offenders, bypassing the
+    /// ladder — the Critical-pressure counterpart to `reclaim_swap_offenders`,
+    /// mirroring how `kill_idle_dev_servers`/`kill_zombie_vms` skip the
+    /// ladder at Critical too.
+    pub fn kill_swap_offenders(&mut self, snapshot: &SystemSnapshot) -> Vec<(String, Pid, u64)> {
+        let candidates = self.unclaimed_swap_candidates(snapshot, PressureLevel::Critical);
+
+        let mut killed = Vec::new();
+        for (name, pid, swap_mb) in candidates {
+            let mem_mb = self
+                .system
+                .process(pid)
+                .map(|proc| proc.memory() / 1_048_576)
+                .unwrap_or(swap_mb);
+            if !self.script_should_kill(&name, pid, mem_mb, PressureLevel::Critical) {
+                continue;
+            }
+            if self.kill_process(pid) {
+                log::warn!(
+                    "Killed top swap offender {} (PID {}, {}MB swapped)",
+                    name,
+                    pid,
+                    swap_mb
+                );
+                killed.push((name, pid, swap_mb));
+            }
+        }
+
+        killed
+    }
+
     /// Generate recommendations based on current snapshot
     pub fn recommendations(&self, snapshot: &SystemSnapshot) -> Vec<String> {
         let mut recs = Vec::new();
 
         if snapshot.dev_server_count > 0 {
             recs.push(format!(
-                "Kill {} dev server(s) to free ~{}MB",
-                snapshot.dev_server_count, snapshot.dev_server_total_mb
+                "Kill {} dev server(s) to free ~{}MB (includes build-worker subtrees)",
+                snapshot.dev_server_count, snapshot.dev_server_total_subtree_mb
             ));
         }
 
-        if snapshot.browser_total_mb > 2000 {
-            recs.push(format!(
-                "Browser using {}MB across {} processes — close tabs or extensions",
-                snapshot.browser_total_mb, snapshot.browser_count
-            ));
+        if snapshot.browser_total_uss_mb > 2000 {
+            match self.find_browser_debug_port() {
+                Some(_) if self.config.auto_kill.idle_browser_tabs => recs.push(format!(
+                    "Browser using {}MB across {} processes — closing idle background tabs over DevTools Protocol",
+                    snapshot.browser_total_uss_mb, snapshot.browser_count
+                )),
+                Some(_) => recs.push(format!(
+                    "Browser using {}MB across {} processes — enable auto_kill.idle_browser_tabs to close idle tabs automatically",
+                    snapshot.browser_total_uss_mb, snapshot.browser_count
+                )),
+                None => recs.push(format!(
+                    "Browser using {}MB across {} processes — relaunch with --remote-debugging-port=<port> so resmgr can close idle tabs instead of just recommending it",
+                    snapshot.browser_total_uss_mb, snapshot.browser_count
+                )),
+            }
         }
 
         if snapshot.node_count > 50 {
             recs.push(format!(
                 "{} node processes ({}MB) — exit unused Claude Code sessions",
-                snapshot.node_count, snapshot.node_total_mb
+                snapshot.node_count, snapshot.node_total_uss_mb
             ));
         }
 
@@ -429,10 +1213,44 @@ impl Monitor {
         }
 
         if snapshot.used_swap_gb > 10.0 {
-            recs.push(format!(
+            let mut msg = format!(
                 "Swap at {:.1}GB — system is memory-thrashing",
                 snapshot.used_swap_gb
-            ));
+            );
+            // At High/Critical pressure, name the top swap offender instead
+            // of just the system-wide total — that's the process whose
+            // reclaim actually relieves the thrash.
+            if snapshot.pressure >= PressureLevel::High {
+                if let Some((name, pid, swap_mb)) = snapshot.top_swap_offenders.first() {
+                    msg.push_str(&format!(
+                        "; top offender: {} (PID {}, {}MB swapped)",
+                        name, pid, swap_mb
+                    ));
+                }
+            }
+            recs.push(msg);
+        }
+
+        // Name the group holding the most anonymous memory — file/shmem
+        // pages can be dropped by the OS under pressure, but anon pages are
+        // only reclaimable by killing (or swapping) the process that holds
+        // them, so that's the group a kill would actually shrink.
+        if let Some((group, stats)) = snapshot
+            .group_stats
+            .iter()
+            .max_by_key(|(_, stats)| stats.anon_mb)
+        {
+            if stats.anon_mb > 1024 {
+                recs.push(format!(
+                    "{} group holds {}MB anonymous memory across {} process(es) — largest unreclaimable-without-kill group",
+                    group, stats.anon_mb, stats.count
+                ));
+            }
+        }
+
+        #[cfg(feature = "scripting")]
+        if let Some(engine) = &self.script_engine {
+            recs.extend(engine.on_sample(snapshot));
         }
 
         recs
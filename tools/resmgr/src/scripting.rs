@@ -0,0 +1,87 @@
+#![cfg(feature = "scripting")]
+
+use crate::monitor::{PressureLevel, SystemSnapshot};
+use mlua::{Function, Lua};
+
+/// Loads a user-supplied Lua script and exposes the `on_sample`/`should_kill`
+/// hooks it defines, so site-specific kill policy can be encoded without
+/// recompiling resmgr.
+pub struct ScriptEngine {
+    lua: Lua,
+}
+
+impl ScriptEngine {
+    pub fn load(path: &str) -> Option<Self> {
+        let src = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => {
+                log::warn!("Failed to read scripting file {}: {}", path, e);
+                return None;
+            }
+        };
+
+        let lua = Lua::new();
+        if let Err(e) = lua.load(&src).exec() {
+            log::warn!("Failed to execute scripting file {}: {}", path, e);
+            return None;
+        }
+
+        log::info!("Loaded scripting policy from {}", path);
+        Some(Self { lua })
+    }
+
+    /// Call the script's `on_sample(snapshot)` hook, if defined, and return
+    /// any extra recommendation strings it produced.
+    pub fn on_sample(&self, snapshot: &SystemSnapshot) -> Vec<String> {
+        let Ok(func) = self.lua.globals().get::<Function>("on_sample") else {
+            return Vec::new();
+        };
+
+        let table = match self.lua.create_table() {
+            Ok(t) => t,
+            Err(_) => return Vec::new(),
+        };
+        let _ = table.set("used_memory_gb", snapshot.used_memory_gb);
+        let _ = table.set("free_memory_gb", snapshot.free_memory_gb);
+        let _ = table.set("used_swap_gb", snapshot.used_swap_gb);
+        let _ = table.set("pressure", snapshot.pressure.to_string());
+        let _ = table.set("node_total_mb", snapshot.node_total_mb);
+        let _ = table.set("node_total_uss_mb", snapshot.node_total_uss_mb);
+        let _ = table.set("browser_total_mb", snapshot.browser_total_mb);
+        let _ = table.set("browser_total_uss_mb", snapshot.browser_total_uss_mb);
+        let _ = table.set("dev_server_total_mb", snapshot.dev_server_total_mb);
+        let _ = table.set("dev_server_total_uss_mb", snapshot.dev_server_total_uss_mb);
+        let _ = table.set(
+            "dev_server_total_subtree_mb",
+            snapshot.dev_server_total_subtree_mb,
+        );
+
+        match func.call::<Vec<String>>(table) {
+            Ok(recs) => recs,
+            Err(e) => {
+                log::warn!("on_sample script error: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Call the script's `should_kill(process, pressure)` hook, if defined.
+    /// Returns `None` when the script doesn't define the hook, so the caller
+    /// falls back to its built-in policy.
+    pub fn should_kill(
+        &self,
+        name: &str,
+        pid: u32,
+        memory_mb: u64,
+        pressure: PressureLevel,
+    ) -> Option<bool> {
+        let func: Function = self.lua.globals().get("should_kill").ok()?;
+
+        let proc_table = self.lua.create_table().ok()?;
+        proc_table.set("name", name).ok()?;
+        proc_table.set("pid", pid).ok()?;
+        proc_table.set("memory_mb", memory_mb).ok()?;
+
+        func.call::<bool>((proc_table, pressure.to_string())).ok()
+    }
+}
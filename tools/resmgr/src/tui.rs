@@ -0,0 +1,314 @@
+use crate::config::Config;
+use crate::monitor::{Monitor, PressureLevel, SystemSnapshot};
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, ExecutableCommand};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table};
+use ratatui::{Frame, Terminal};
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+const HISTORY_LEN: usize = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortColumn {
+    Memory,
+    Cpu,
+    Name,
+}
+
+impl SortColumn {
+    fn next(self) -> Self {
+        match self {
+            SortColumn::Memory => SortColumn::Cpu,
+            SortColumn::Cpu => SortColumn::Name,
+            SortColumn::Name => SortColumn::Memory,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortColumn::Memory => "memory",
+            SortColumn::Cpu => "cpu",
+            SortColumn::Name => "name",
+        }
+    }
+}
+
+struct DashboardState {
+    used_history: VecDeque<u64>,
+    free_history: VecDeque<u64>,
+    swap_history: VecDeque<u64>,
+    paused: bool,
+    sort: SortColumn,
+    last_killed: Option<String>,
+}
+
+impl DashboardState {
+    fn new() -> Self {
+        Self {
+            used_history: VecDeque::with_capacity(HISTORY_LEN),
+            free_history: VecDeque::with_capacity(HISTORY_LEN),
+            swap_history: VecDeque::with_capacity(HISTORY_LEN),
+            paused: false,
+            sort: SortColumn::Memory,
+            last_killed: None,
+        }
+    }
+
+    fn push(&mut self, snapshot: &SystemSnapshot) {
+        push_capped(&mut self.used_history, (snapshot.used_memory_gb * 1024.0) as u64);
+        push_capped(&mut self.free_history, (snapshot.free_memory_gb * 1024.0) as u64);
+        push_capped(&mut self.swap_history, (snapshot.used_swap_gb * 1024.0) as u64);
+    }
+}
+
+fn push_capped(buf: &mut VecDeque<u64>, value: u64) {
+    if buf.len() >= HISTORY_LEN {
+        buf.pop_front();
+    }
+    buf.push_back(value);
+}
+
+fn pressure_color(level: PressureLevel) -> Color {
+    match level {
+        PressureLevel::Normal => Color::Green,
+        PressureLevel::Elevated => Color::Yellow,
+        PressureLevel::High => Color::LightRed,
+        PressureLevel::Critical => Color::Red,
+    }
+}
+
+/// Run the full-screen dashboard. `basic_mode` drops the sparkline graphs and
+/// condenses everything to plain rows, for narrow terminals or when logging
+/// the session to a file.
+pub fn run(config: Config, basic_mode: bool) -> io::Result<()> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = run_loop(&mut terminal, config, basic_mode);
+
+    disable_raw_mode()?;
+    io::stdout().execute(LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    result
+}
+
+fn run_loop(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    config: Config,
+    basic_mode: bool,
+) -> io::Result<()> {
+    let mut monitor = Monitor::new(config.clone());
+    let mut state = DashboardState::new();
+    let poll_interval = Duration::from_secs(config.general.poll_interval_seconds);
+    let mut last_sample = Instant::now() - poll_interval;
+    let mut snapshot = monitor.sample();
+    state.push(&snapshot);
+
+    loop {
+        if event::poll(Duration::from_millis(200))? {
+            if let Event::Key(key) = event::read()? {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('p') => state.paused = !state.paused,
+                    KeyCode::Char('k') => {
+                        let killed = monitor.kill_idle_dev_servers(snapshot.pressure);
+                        state.last_killed = if killed.is_empty() {
+                            Some("no idle dev servers to kill".to_string())
+                        } else {
+                            let names: Vec<String> = killed
+                                .iter()
+                                .map(|(name, pid, mb)| format!("{} [{}] ({}MB)", name, pid, mb))
+                                .collect();
+                            Some(format!("killed: {}", names.join(", ")))
+                        };
+                    }
+                    KeyCode::Char('s') => state.sort = state.sort.next(),
+                    _ => {}
+                }
+            }
+        }
+
+        if !state.paused && last_sample.elapsed() >= poll_interval {
+            snapshot = monitor.sample();
+            state.push(&snapshot);
+            last_sample = Instant::now();
+        }
+
+        let recs = monitor.recommendations(&snapshot);
+        terminal.draw(|frame| {
+            if basic_mode {
+                draw_basic(frame, &snapshot, &state, &recs);
+            } else {
+                draw_full(frame, &snapshot, &state, &recs);
+            }
+        })?;
+    }
+}
+
+fn sorted_processes(
+    snapshot: &SystemSnapshot,
+    sort: SortColumn,
+) -> Vec<&crate::monitor::ProcessInfo> {
+    let mut procs: Vec<&crate::monitor::ProcessInfo> = snapshot.top_processes.iter().collect();
+    match sort {
+        SortColumn::Memory => procs.sort_by_key(|p| std::cmp::Reverse(p.memory_mb)),
+        SortColumn::Cpu => procs.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent)),
+        SortColumn::Name => procs.sort_by(|a, b| a.name.cmp(&b.name)),
+    }
+    procs
+}
+
+fn draw_full(frame: &mut Frame, snapshot: &SystemSnapshot, state: &DashboardState, recs: &[String]) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(7),
+            Constraint::Min(6),
+            Constraint::Length(recs.len() as u16 + 2),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
+    draw_header(frame, rows[0], snapshot, state);
+    draw_sparklines(frame, rows[1], state);
+    draw_process_table(frame, rows[2], snapshot, state);
+    draw_recommendations(frame, rows[3], recs);
+    draw_footer(frame, rows[4], state);
+}
+
+fn draw_basic(frame: &mut Frame, snapshot: &SystemSnapshot, state: &DashboardState, recs: &[String]) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(6),
+            Constraint::Length(recs.len() as u16 + 2),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
+    draw_header(frame, rows[0], snapshot, state);
+    draw_process_table(frame, rows[1], snapshot, state);
+    draw_recommendations(frame, rows[2], recs);
+    draw_footer(frame, rows[3], state);
+}
+
+fn draw_header(frame: &mut Frame, area: ratatui::layout::Rect, snapshot: &SystemSnapshot, state: &DashboardState) {
+    let paused = if state.paused { " [PAUSED]" } else { "" };
+    let text = Line::from(vec![
+        Span::raw(format!(
+            "Mem {:.1}/{:.1} GB  Swap {:.1}/{:.1} GB  ",
+            snapshot.used_memory_gb,
+            snapshot.total_memory_gb,
+            snapshot.used_swap_gb,
+            snapshot.total_swap_gb
+        )),
+        Span::styled(
+            format!("{}", snapshot.pressure),
+            Style::default()
+                .fg(pressure_color(snapshot.pressure))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(paused),
+    ]);
+    let block = Block::default().borders(Borders::ALL).title("resmgr top");
+    frame.render_widget(Paragraph::new(text).block(block), area);
+}
+
+fn draw_sparklines(frame: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let cols = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let used: Vec<u64> = state.used_history.iter().copied().collect();
+    let used_sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title("Used MB"))
+        .data(&used)
+        .style(Style::default().fg(Color::Cyan));
+    frame.render_widget(used_sparkline, cols[0]);
+
+    let swap: Vec<u64> = state.swap_history.iter().copied().collect();
+    let swap_sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title("Swap MB"))
+        .data(&swap)
+        .style(Style::default().fg(Color::Magenta));
+    frame.render_widget(swap_sparkline, cols[1]);
+}
+
+fn draw_process_table(frame: &mut Frame, area: ratatui::layout::Rect, snapshot: &SystemSnapshot, state: &DashboardState) {
+    let header = Row::new(vec!["PID", "MEM (MB)", "SUBTREE (MB)", "CPU %", "NAME"]).style(
+        Style::default().add_modifier(Modifier::BOLD),
+    );
+    let rows: Vec<Row> = sorted_processes(snapshot, state.sort)
+        .into_iter()
+        .take(area.height.saturating_sub(3) as usize)
+        .map(|p| {
+            let subtree = if p.child_pids.is_empty() {
+                String::new()
+            } else {
+                format!("{} ({})", p.subtree_memory_mb, p.child_pids.len())
+            };
+            Row::new(vec![
+                Cell::from(p.pid.to_string()),
+                Cell::from(p.memory_mb.to_string()),
+                Cell::from(subtree),
+                Cell::from(format!("{:.1}", p.cpu_percent)),
+                Cell::from(p.name.clone()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Processes (sorted by {})",
+        state.sort.label()
+    )));
+
+    frame.render_widget(table, area);
+}
+
+fn draw_recommendations(frame: &mut Frame, area: ratatui::layout::Rect, recs: &[String]) {
+    let lines: Vec<Line> = if recs.is_empty() {
+        vec![Line::from("No recommendations")]
+    } else {
+        recs.iter().map(|r| Line::from(format!("* {}", r))).collect()
+    };
+    let block = Block::default().borders(Borders::ALL).title("Recommendations");
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_footer(frame: &mut Frame, area: ratatui::layout::Rect, state: &DashboardState) {
+    let killed = state
+        .last_killed
+        .as_deref()
+        .map(|s| format!("  last: {}", s))
+        .unwrap_or_default();
+    let text = format!(
+        "[q]uit  [p]ause/resume  [k]ill idle dev servers  [s]ort column{}",
+        killed
+    );
+    frame.render_widget(Paragraph::new(text), area);
+}